@@ -0,0 +1,24 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    InvalidProof = 1,
+    UnknownSession = 2,
+    BoardRootMismatch = 3,
+    NullifierAlreadySpent = 4,
+    SessionNotInProgress = 5,
+    CellAlreadyFired = 6,
+    ShotAlreadyPending = 7,
+    NoPendingShot = 8,
+    ShipAlreadySunk = 9,
+    UnsanctionedImage = 10,
+    NotInitialized = 11,
+    VerifyingKeyNotSet = 12,
+    ImageIdNotSet = 13,
+    SessionNotAwaitingCommit = 14,
+    BoardAlreadyCommitted = 15,
+    BoardNotCommitted = 16,
+    ShotResponseNotTimedOut = 17,
+}
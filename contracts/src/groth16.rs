@@ -0,0 +1,108 @@
+use soroban_sdk::crypto::bls12_381::{Fr, G1Affine, G2Affine};
+use soroban_sdk::{Bytes, BytesN, Env, Vec};
+
+use crate::error::Error;
+
+/// Order `r` of the BLS12-381 scalar field, used to negate G1 points
+/// (there is no dedicated host negation function, so we multiply by `r - 1`).
+pub(crate) const FR_MODULUS: [u8; 32] = [
+    0x73, 0xed, 0xa7, 0x53, 0x29, 0x9d, 0x7d, 0x48, 0x33, 0x39, 0xd8, 0x08, 0x09, 0xa1, 0xd8, 0x05,
+    0x53, 0xbd, 0xa4, 0x02, 0xff, 0xfe, 0x5b, 0xfe, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01,
+];
+
+pub struct VerifyingKey {
+    pub alpha_g1: G1Affine,
+    pub beta_g2: G2Affine,
+    pub gamma_g2: G2Affine,
+    pub delta_g2: G2Affine,
+    /// `IC[0]` is the constant term, `IC[1..]` line up with the public inputs.
+    pub ic: Vec<G1Affine>,
+}
+
+pub struct Proof {
+    pub a: G1Affine,
+    pub b: G2Affine,
+    pub c: G1Affine,
+}
+
+fn g1_neg(env: &Env, p: &G1Affine) -> G1Affine {
+    let bls = env.crypto().bls12_381();
+    let mut order_minus_one = FR_MODULUS;
+    order_minus_one[31] -= 1;
+    let scalar = Fr::from_bytes(BytesN::from_array(env, &order_minus_one));
+    bls.g1_mul(p, &scalar)
+}
+
+fn parse_proof(env: &Env, proof: &Bytes) -> Result<Proof, Error> {
+    if proof.len() != 96 + 192 + 96 {
+        return Err(Error::InvalidProof);
+    }
+    let a: [u8; 96] = proof.slice(0..96).try_into().map_err(|_| Error::InvalidProof)?;
+    let b: [u8; 192] = proof.slice(96..288).try_into().map_err(|_| Error::InvalidProof)?;
+    let c: [u8; 96] = proof.slice(288..384).try_into().map_err(|_| Error::InvalidProof)?;
+    Ok(Proof {
+        a: G1Affine::from_array(env, &a),
+        b: G2Affine::from_array(env, &b),
+        c: G1Affine::from_array(env, &c),
+    })
+}
+
+fn parse_public_inputs(env: &Env, public_inputs: &Vec<Bytes>) -> Result<Vec<Fr>, Error> {
+    let mut scalars = Vec::new(env);
+    for input in public_inputs.iter() {
+        if input.len() != 32 {
+            return Err(Error::InvalidProof);
+        }
+        let bytes: [u8; 32] = input.try_into().map_err(|_| Error::InvalidProof)?;
+        scalars.push_back(Fr::from_bytes(BytesN::from_array(env, &bytes)));
+    }
+    Ok(scalars)
+}
+
+/// Computes `vk_x = IC[0] + sum(input_i * IC[i+1])`.
+fn compute_vk_x(env: &Env, vk: &VerifyingKey, inputs: &Vec<Fr>) -> Result<G1Affine, Error> {
+    if inputs.len() as usize + 1 != vk.ic.len() as usize {
+        return Err(Error::InvalidProof);
+    }
+    let bls = env.crypto().bls12_381();
+    let mut vk_x = vk.ic.get(0).unwrap();
+    for i in 0..inputs.len() {
+        let term = bls.g1_mul(&vk.ic.get(i + 1).unwrap(), &inputs.get(i).unwrap());
+        vk_x = bls.g1_add(&vk_x, &term);
+    }
+    Ok(vk_x)
+}
+
+/// Verifies a Groth16 proof over BLS12-381 against `vk`, using the host's
+/// pairing check: `e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1`.
+pub fn verify(
+    env: &Env,
+    vk: &VerifyingKey,
+    proof: &Bytes,
+    public_inputs: &Vec<Bytes>,
+) -> Result<(), Error> {
+    let proof = parse_proof(env, proof)?;
+    let inputs = parse_public_inputs(env, public_inputs)?;
+    let vk_x = compute_vk_x(env, vk, &inputs)?;
+
+    let bls = env.crypto().bls12_381();
+    let neg_a = g1_neg(env, &proof.a);
+
+    let mut g1_points = Vec::new(env);
+    g1_points.push_back(neg_a);
+    g1_points.push_back(vk.alpha_g1.clone());
+    g1_points.push_back(vk_x);
+    g1_points.push_back(proof.c);
+
+    let mut g2_points = Vec::new(env);
+    g2_points.push_back(proof.b);
+    g2_points.push_back(vk.beta_g2.clone());
+    g2_points.push_back(vk.gamma_g2.clone());
+    g2_points.push_back(vk.delta_g2.clone());
+
+    if bls.pairing_check(g1_points, g2_points) {
+        Ok(())
+    } else {
+        Err(Error::InvalidProof)
+    }
+}
@@ -1,43 +1,365 @@
-#![no_std]
-use soroban_sdk::{contract, contractimpl, Address, Env, Bytes, Vec, String};
-
-mod hub {
-    soroban_sdk::contractimport!(file = "../game_hub.wasm");
-}
-
-#[contract]
-pub struct ZKStrategyGame;
-
-#[contractimpl]
-impl ZKStrategyGame {
-    pub fn start_game(
-        env: Env,
-        player1: Address,
-        player2: Address,
-        session_id: u32,
-        player1_points: i128,
-        player2_points: i128,
-    ) {
-        player1.require_auth();
-        let hub_addr = Address::from_string(&String::from_str(&env, "CB4VZAT2U3UC6XFK3N23SKRF2NDCMP3QHJYMCHHFMZO7MRQO6DQ2EMYG"));
-        let hub_client = hub::Client::new(&env, &hub_addr);
-        hub_client.start_game(
-            &env.current_contract_address(), // game_id = this contract
-            &session_id,
-            &player1,
-            &player2,
-            &player1_points,
-            &player2_points,
-        );
-    }
-
-    pub fn verify_miss(_env: Env, _proof: Bytes, _public_inputs: Vec<Bytes>) {
-        // ZK Verification Logic
-    }
-
-    pub fn end_game(env: Env, session_id: u32, player1_won: bool) {
-        let hub_addr = Address::from_string(&String::from_str(&env, "CB4VZAT2U3UC6XFK3N23SKRF2NDCMP3QHJYMCHHFMZO7MRQO6DQ2EMYG"));
-        let hub_client = hub::Client::new(&env, &hub_addr);
-        hub_client.end_game(&session_id, &player1_won);
-    }
-}
+#![no_std]
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Bytes, Vec, String};
+
+mod error;
+mod groth16;
+mod risc0;
+mod state;
+mod storage;
+#[cfg(test)]
+mod test;
+mod vk;
+
+pub use error::Error;
+use state::{GameSession, Status, TOTAL_SHIPS, TOTAL_SHIP_CELLS};
+use storage::DataKey;
+
+mod hub {
+    soroban_sdk::contractimport!(file = "../game_hub.wasm");
+}
+
+fn hub_client(env: &Env) -> hub::Client {
+    let hub_addr = Address::from_string(&String::from_str(
+        env,
+        "CB4VZAT2U3UC6XFK3N23SKRF2NDCMP3QHJYMCHHFMZO7MRQO6DQ2EMYG",
+    ));
+    hub::Client::new(env, &hub_addr)
+}
+
+fn load_session(env: &Env, session_id: u32) -> Result<GameSession, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Session(session_id))
+        .ok_or(Error::UnknownSession)
+}
+
+fn save_session(env: &Env, session_id: u32, session: &GameSession) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Session(session_id), session);
+}
+
+fn require_participant(session: &GameSession, address: &Address) -> Result<(), Error> {
+    if address == &session.player1 || address == &session.player2 {
+        Ok(())
+    } else {
+        Err(Error::UnknownSession)
+    }
+}
+
+/// Encodes a cell index as the 32-byte big-endian field element the Miss/Hit
+/// circuits take as their cell-binding public input.
+fn encode_cell(cell: u32) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[28..32].copy_from_slice(&cell.to_be_bytes());
+    bytes
+}
+
+/// Records the outcome of a resolved shot and, if it finished the game,
+/// reports the winner to the hub itself rather than trusting a caller.
+fn resolve_and_maybe_finish(env: &Env, session_id: u32, session: &mut GameSession, cell: u32, hit: bool) {
+    if let Some(winner) = session.resolve_shot(cell, hit) {
+        let player1_won = session.is_player1(&winner);
+        hub_client(env).end_game(&session_id, &player1_won);
+    }
+}
+
+/// Shared resolution path for `verify_miss`/`verify_hit`: both prove a claim
+/// about the pending shot against the defender's committed root, using a
+/// `[root, nullifier, cell]` public-input schema, and differ only in their
+/// verifying key and whether the shot counts as a hit. The cell input binds
+/// the proof to `session.pending_shot` so a defender can't prove a claim
+/// about some other cell and have it applied to the real pending shot.
+fn verify_shot(
+    env: &Env,
+    session_id: u32,
+    vk: &groth16::VerifyingKey,
+    proof: &Bytes,
+    public_inputs: &Vec<Bytes>,
+    hit: bool,
+) -> Result<(), Error> {
+    let mut session = load_session(env, session_id)?;
+    if session.status != Status::InProgress {
+        return Err(Error::SessionNotInProgress);
+    }
+    let cell = session.pending_shot.ok_or(Error::NoPendingShot)?;
+    let defender = session.other_player(&session.whose_turn);
+    defender.require_auth();
+
+    let stored_root = if session.is_player1(&defender) {
+        session.player1_board_root.clone()
+    } else {
+        session.player2_board_root.clone()
+    }
+    .ok_or(Error::BoardNotCommitted)?;
+    let supplied_root = public_inputs.get(0).ok_or(Error::InvalidProof)?;
+    if supplied_root != Bytes::from(stored_root) {
+        return Err(Error::BoardRootMismatch);
+    }
+
+    let supplied_cell: [u8; 32] = public_inputs
+        .get(2)
+        .ok_or(Error::InvalidProof)?
+        .try_into()
+        .map_err(|_| Error::InvalidProof)?;
+    if supplied_cell != encode_cell(cell) {
+        return Err(Error::InvalidProof);
+    }
+
+    let nullifier: BytesN<32> = public_inputs
+        .get(1)
+        .ok_or(Error::InvalidProof)?
+        .try_into()
+        .map_err(|_| Error::InvalidProof)?;
+    let nullifier_key = DataKey::Nullifier(session_id, nullifier);
+    if env.storage().persistent().has(&nullifier_key) {
+        return Err(Error::NullifierAlreadySpent);
+    }
+
+    groth16::verify(env, vk, proof, public_inputs)?;
+    env.storage().persistent().set(&nullifier_key, &true);
+
+    resolve_and_maybe_finish(env, session_id, &mut session, cell, hit);
+    save_session(env, session_id, &session);
+    Ok(())
+}
+
+#[contract]
+pub struct ZKStrategyGame;
+
+#[contractimpl]
+impl ZKStrategyGame {
+    /// Sets the admin allowed to load verifying keys and the sanctioned
+    /// RISC Zero image id. Must be called once before any circuit is usable.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        vk::set_admin(&env, admin)
+    }
+
+    pub fn set_verifying_key(
+        env: Env,
+        circuit: vk::Circuit,
+        verifying_key: vk::StoredVerifyingKey,
+    ) -> Result<(), Error> {
+        vk::set_verifying_key(&env, circuit, verifying_key)
+    }
+
+    pub fn set_expected_image_id(env: Env, image_id: BytesN<32>) -> Result<(), Error> {
+        vk::set_expected_image_id(&env, image_id)
+    }
+
+    /// Reads back a session's current phase (`AwaitingCommit`, `InProgress`,
+    /// or `Finished`), so callers can tell board-commitment from active play
+    /// without inspecting the whole session.
+    pub fn session_status(env: Env, session_id: u32) -> Result<Status, Error> {
+        Ok(load_session(&env, session_id)?.status)
+    }
+
+    pub fn start_game(
+        env: Env,
+        player1: Address,
+        player2: Address,
+        session_id: u32,
+        player1_points: i128,
+        player2_points: i128,
+    ) {
+        player1.require_auth();
+
+        let session = GameSession {
+            player1: player1.clone(),
+            player2: player2.clone(),
+            player1_board_root: None,
+            player2_board_root: None,
+            whose_turn: player1.clone(),
+            player1_hits_remaining: TOTAL_SHIP_CELLS,
+            player2_hits_remaining: TOTAL_SHIP_CELLS,
+            player1_ships_remaining: TOTAL_SHIPS,
+            player2_ships_remaining: TOTAL_SHIPS,
+            player1_fired: Vec::new(&env),
+            player2_fired: Vec::new(&env),
+            pending_shot: None,
+            pending_shot_deadline: None,
+            status: Status::AwaitingCommit,
+        };
+        save_session(&env, session_id, &session);
+
+        hub_client(&env).start_game(
+            &env.current_contract_address(), // game_id = this contract
+            &session_id,
+            &player1,
+            &player2,
+            &player1_points,
+            &player2_points,
+        );
+    }
+
+    /// Commits `player`'s own Poseidon board root; each player must call this
+    /// themselves, since `start_game` no longer accepts roots on a player's
+    /// behalf. Play begins once both players have committed.
+    pub fn commit_board(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        board_root: BytesN<32>,
+    ) -> Result<(), Error> {
+        let mut session = load_session(&env, session_id)?;
+        require_participant(&session, &player)?;
+        player.require_auth();
+
+        session.commit_board(&player, board_root)?;
+        save_session(&env, session_id, &session);
+        Ok(())
+    }
+
+    /// Declares the active player's target cell for this turn; the shot is
+    /// resolved once the defender submits a matching proof.
+    pub fn fire(env: Env, session_id: u32, cell: u32) -> Result<(), Error> {
+        let mut session = load_session(&env, session_id)?;
+        if session.status != Status::InProgress {
+            return Err(Error::SessionNotInProgress);
+        }
+        session.whose_turn.require_auth();
+
+        if session.pending_shot.is_some() {
+            return Err(Error::ShotAlreadyPending);
+        }
+        if session.fired_by(&session.whose_turn).contains(cell) {
+            return Err(Error::CellAlreadyFired);
+        }
+
+        session.pending_shot = Some(cell);
+        session.pending_shot_deadline =
+            Some(env.ledger().timestamp() + state::SHOT_RESPONSE_TIMEOUT_SECONDS);
+        save_session(&env, session_id, &session);
+        Ok(())
+    }
+
+    /// Lets the shooter claim a pending shot as a hit by default once the
+    /// defender has missed the `SHOT_RESPONSE_TIMEOUT_SECONDS` window to
+    /// answer it with a proof, so a defender can't stall a losing game
+    /// forever by simply never responding.
+    pub fn claim_shot_timeout(env: Env, session_id: u32) -> Result<(), Error> {
+        let mut session = load_session(&env, session_id)?;
+        if session.status != Status::InProgress {
+            return Err(Error::SessionNotInProgress);
+        }
+        session.whose_turn.require_auth();
+
+        let cell = session.pending_shot.ok_or(Error::NoPendingShot)?;
+        let deadline = session.pending_shot_deadline.ok_or(Error::NoPendingShot)?;
+        if env.ledger().timestamp() < deadline {
+            return Err(Error::ShotResponseNotTimedOut);
+        }
+
+        resolve_and_maybe_finish(&env, session_id, &mut session, cell, true);
+        save_session(&env, session_id, &session);
+        Ok(())
+    }
+
+    pub fn verify_miss(
+        env: Env,
+        session_id: u32,
+        proof: Bytes,
+        public_inputs: Vec<Bytes>,
+    ) -> Result<(), Error> {
+        let vk = vk::load_verifying_key(&env, vk::Circuit::Miss)?;
+        verify_shot(&env, session_id, &vk, &proof, &public_inputs, false)
+    }
+
+    pub fn verify_hit(
+        env: Env,
+        session_id: u32,
+        proof: Bytes,
+        public_inputs: Vec<Bytes>,
+    ) -> Result<(), Error> {
+        let vk = vk::load_verifying_key(&env, vk::Circuit::Hit)?;
+        verify_shot(&env, session_id, &vk, &proof, &public_inputs, true)
+    }
+
+    /// Proves that a whole ship (a set of previously-hit cells) has been
+    /// fully destroyed, against the defender's committed root. Unlike
+    /// `verify_miss`/`verify_hit`, this is not tied to a pending shot.
+    pub fn verify_sink(
+        env: Env,
+        session_id: u32,
+        defender: Address,
+        proof: Bytes,
+        public_inputs: Vec<Bytes>,
+    ) -> Result<(), Error> {
+        let mut session = load_session(&env, session_id)?;
+        if session.status != Status::InProgress {
+            return Err(Error::SessionNotInProgress);
+        }
+        require_participant(&session, &defender)?;
+        defender.require_auth();
+
+        let defender_ships_remaining = if session.is_player1(&defender) {
+            session.player1_ships_remaining
+        } else {
+            session.player2_ships_remaining
+        };
+        if defender_ships_remaining == 0 {
+            return Err(Error::ShipAlreadySunk);
+        }
+
+        let stored_root = if session.is_player1(&defender) {
+            session.player1_board_root.clone()
+        } else {
+            session.player2_board_root.clone()
+        }
+        .ok_or(Error::BoardNotCommitted)?;
+        let supplied_root = public_inputs.get(0).ok_or(Error::InvalidProof)?;
+        if supplied_root != Bytes::from(stored_root) {
+            return Err(Error::BoardRootMismatch);
+        }
+
+        let nullifier: BytesN<32> = public_inputs
+            .get(2)
+            .ok_or(Error::InvalidProof)?
+            .try_into()
+            .map_err(|_| Error::InvalidProof)?;
+        let nullifier_key = DataKey::Nullifier(session_id, nullifier);
+        if env.storage().persistent().has(&nullifier_key) {
+            return Err(Error::NullifierAlreadySpent);
+        }
+
+        let vk = vk::load_verifying_key(&env, vk::Circuit::Sink)?;
+        groth16::verify(&env, &vk, &proof, &public_inputs)?;
+        env.storage().persistent().set(&nullifier_key, &true);
+
+        session.record_sink(&defender);
+        save_session(&env, session_id, &session);
+        Ok(())
+    }
+
+    /// Alternative proving backend: verifies a RISC Zero receipt whose STARK
+    /// has been wrapped into a Groth16 seal, rather than a hand-written
+    /// circuit, and applies the game-relevant fields it commits to in its
+    /// journal the same way `verify_miss`/`verify_hit` apply a direct proof.
+    pub fn verify_miss_r0(
+        env: Env,
+        image_id: BytesN<32>,
+        journal: Bytes,
+        seal: Bytes,
+    ) -> Result<(), Error> {
+        if image_id != vk::expected_image_id(&env)? {
+            return Err(Error::UnsanctionedImage);
+        }
+
+        let r0_vk = vk::load_verifying_key(&env, vk::Circuit::Risc0)?;
+        risc0::verify_receipt(&env, &r0_vk, &image_id, &journal, &seal)?;
+        let fields = risc0::GameJournal::decode(&env, &journal)?;
+
+        let mut session = load_session(&env, fields.session_id)?;
+        if session.status != Status::InProgress {
+            return Err(Error::SessionNotInProgress);
+        }
+        session
+            .other_player(&session.whose_turn)
+            .require_auth();
+
+        let (cell, hit) = risc0::apply_journal(&session, &fields)?;
+        resolve_and_maybe_finish(&env, fields.session_id, &mut session, cell, hit);
+        save_session(&env, fields.session_id, &session);
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+//! RISC Zero receipt verification: the guest program's STARK has been
+//! wrapped into a Groth16 seal, so verifying it reuses the same
+//! `groth16::verify` pairing check, just with a RISC Zero-specific
+//! verifying key binding the seal to `(image_id, sha256(journal))`. That
+//! claim digest is a full 32-byte SHA-256 output, wider than the BLS12-381
+//! scalar field, so it is split into two field-safe limbs and passed as two
+//! public inputs rather than risking one input overflowing the modulus.
+use soroban_sdk::{Bytes, BytesN, Env, Vec};
+
+use crate::error::Error;
+use crate::groth16::{self, VerifyingKey};
+use crate::state::GameSession;
+
+/// The game-relevant fields the guest program commits to in its journal:
+/// `session_id | cell | hit | board_root`.
+pub struct GameJournal {
+    pub session_id: u32,
+    pub cell: u32,
+    pub hit: bool,
+    pub board_root: BytesN<32>,
+}
+
+const JOURNAL_LEN: u32 = 4 + 4 + 1 + 32;
+
+impl GameJournal {
+    pub fn decode(env: &Env, journal: &Bytes) -> Result<Self, Error> {
+        if journal.len() != JOURNAL_LEN {
+            return Err(Error::InvalidProof);
+        }
+        let session_bytes: [u8; 4] = journal
+            .slice(0..4)
+            .try_into()
+            .map_err(|_| Error::InvalidProof)?;
+        let cell_bytes: [u8; 4] = journal
+            .slice(4..8)
+            .try_into()
+            .map_err(|_| Error::InvalidProof)?;
+        let hit_byte = journal.get(8).ok_or(Error::InvalidProof)?;
+        let root_bytes: [u8; 32] = journal
+            .slice(9..41)
+            .try_into()
+            .map_err(|_| Error::InvalidProof)?;
+
+        Ok(Self {
+            session_id: u32::from_be_bytes(session_bytes),
+            cell: u32::from_be_bytes(cell_bytes),
+            hit: hit_byte != 0,
+            board_root: BytesN::from_array(env, &root_bytes),
+        })
+    }
+}
+
+/// `sha256(image_id || sha256(journal))`, the claim digest the RISC Zero
+/// Groth16 wrapper binds its seal to.
+fn claim_digest(env: &Env, image_id: &BytesN<32>, journal: &Bytes) -> BytesN<32> {
+    let journal_digest = env.crypto().sha256(journal);
+    let mut preimage = Bytes::from(image_id.clone());
+    preimage.append(&Bytes::from(journal_digest));
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Splits a 32-byte digest into its high and low 16-byte halves, each
+/// zero-extended back out to 32 bytes. A raw 32-byte SHA-256 output is a full
+/// 256-bit value with roughly even odds of landing at or past the ~2^255
+/// BLS12-381 scalar field modulus, so a legitimate digest can't safely be fed
+/// in as a single field element; splitting it into two 128-bit limbs keeps
+/// each one comfortably inside the field.
+fn split_digest(env: &Env, digest: &BytesN<32>) -> (BytesN<32>, BytesN<32>) {
+    let bytes = digest.to_array();
+    let mut high = [0u8; 32];
+    let mut low = [0u8; 32];
+    high[16..32].copy_from_slice(&bytes[0..16]);
+    low[16..32].copy_from_slice(&bytes[16..32]);
+    (BytesN::from_array(env, &high), BytesN::from_array(env, &low))
+}
+
+pub fn verify_receipt(
+    env: &Env,
+    vk: &VerifyingKey,
+    image_id: &BytesN<32>,
+    journal: &Bytes,
+    seal: &Bytes,
+) -> Result<(), Error> {
+    let digest = claim_digest(env, image_id, journal);
+    let (digest_high, digest_low) = split_digest(env, &digest);
+    let mut public_inputs = Vec::new(env);
+    public_inputs.push_back(Bytes::from(digest_high));
+    public_inputs.push_back(Bytes::from(digest_low));
+    groth16::verify(env, vk, seal, &public_inputs)
+}
+
+/// Applies a decoded journal to the session the same way `verify_shot` does,
+/// cross-checking the pending shot's cell and the defender's committed root.
+pub fn apply_journal(
+    session: &GameSession,
+    fields: &GameJournal,
+) -> Result<(u32, bool), Error> {
+    let cell = session.pending_shot.ok_or(Error::NoPendingShot)?;
+    if cell != fields.cell {
+        return Err(Error::InvalidProof);
+    }
+
+    let defender = session.other_player(&session.whose_turn);
+    let stored_root = if session.is_player1(&defender) {
+        session.player1_board_root.clone()
+    } else {
+        session.player2_board_root.clone()
+    }
+    .ok_or(Error::BoardNotCommitted)?;
+    if fields.board_root != stored_root {
+        return Err(Error::BoardRootMismatch);
+    }
+
+    Ok((cell, fields.hit))
+}
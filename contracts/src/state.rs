@@ -0,0 +1,144 @@
+use soroban_sdk::{contracttype, Address, BytesN, Vec};
+
+use crate::error::Error;
+
+/// Total ship cells a player must lose (5+4+3+3+2, classic Battleship fleet)
+/// before their opponent wins.
+pub const TOTAL_SHIP_CELLS: u32 = 17;
+
+/// Number of distinct ships in the classic Battleship fleet.
+pub const TOTAL_SHIPS: u32 = 5;
+
+/// Seconds a defender has to answer a pending shot with a miss/hit proof
+/// before the shooter can claim it as a hit by default. Without this, a
+/// defender who is about to lose could simply never respond and soft-lock
+/// the session forever.
+pub const SHOT_RESPONSE_TIMEOUT_SECONDS: u64 = 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    AwaitingCommit,
+    InProgress,
+    Finished,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct GameSession {
+    pub player1: Address,
+    pub player2: Address,
+    /// `None` until the player calls `commit_board`; both must be set before
+    /// the session leaves `Status::AwaitingCommit`.
+    pub player1_board_root: Option<BytesN<32>>,
+    pub player2_board_root: Option<BytesN<32>>,
+    pub whose_turn: Address,
+    pub player1_hits_remaining: u32,
+    pub player2_hits_remaining: u32,
+    pub player1_ships_remaining: u32,
+    pub player2_ships_remaining: u32,
+    pub player1_fired: Vec<u32>,
+    pub player2_fired: Vec<u32>,
+    pub pending_shot: Option<u32>,
+    /// Ledger timestamp after which the shooter can claim a still-pending
+    /// shot as a default hit via `claim_shot_timeout`, set whenever
+    /// `pending_shot` is set.
+    pub pending_shot_deadline: Option<u64>,
+    pub status: Status,
+}
+
+impl GameSession {
+    pub fn is_player1(&self, player: &Address) -> bool {
+        player == &self.player1
+    }
+
+    pub fn other_player(&self, player: &Address) -> Address {
+        if self.is_player1(player) {
+            self.player2.clone()
+        } else {
+            self.player1.clone()
+        }
+    }
+
+    pub fn fired_by(&self, player: &Address) -> Vec<u32> {
+        if self.is_player1(player) {
+            self.player1_fired.clone()
+        } else {
+            self.player2_fired.clone()
+        }
+    }
+
+    /// Records the shooter's shot at `cell`, clears the pending shot, and
+    /// decrements the defender's remaining ship cells on a hit. The turn then
+    /// passes to the defender, hit or miss, unless the shot destroyed the
+    /// defender's last ship cell: then the session is finished and the
+    /// shooter is returned as the winner.
+    pub fn resolve_shot(&mut self, cell: u32, hit: bool) -> Option<Address> {
+        let shooter = self.whose_turn.clone();
+        let defender = self.other_player(&shooter);
+
+        if self.is_player1(&shooter) {
+            self.player1_fired.push_back(cell);
+        } else {
+            self.player2_fired.push_back(cell);
+        }
+
+        if hit {
+            if self.is_player1(&defender) {
+                self.player1_hits_remaining -= 1;
+            } else {
+                self.player2_hits_remaining -= 1;
+            }
+        }
+
+        self.pending_shot = None;
+        self.pending_shot_deadline = None;
+
+        let defender_hits_remaining = if self.is_player1(&defender) {
+            self.player1_hits_remaining
+        } else {
+            self.player2_hits_remaining
+        };
+
+        if hit && defender_hits_remaining == 0 {
+            self.status = Status::Finished;
+            Some(shooter)
+        } else {
+            self.whose_turn = defender;
+            None
+        }
+    }
+
+    /// Records that one of the defender's ships has been fully destroyed.
+    pub fn record_sink(&mut self, defender: &Address) {
+        if self.is_player1(defender) {
+            self.player1_ships_remaining -= 1;
+        } else {
+            self.player2_ships_remaining -= 1;
+        }
+    }
+
+    /// Records `player`'s board commitment. Once both players have
+    /// committed, the session leaves `Status::AwaitingCommit` and play can
+    /// begin.
+    pub fn commit_board(&mut self, player: &Address, root: BytesN<32>) -> Result<(), Error> {
+        if self.status != Status::AwaitingCommit {
+            return Err(Error::SessionNotAwaitingCommit);
+        }
+
+        let slot = if self.is_player1(player) {
+            &mut self.player1_board_root
+        } else {
+            &mut self.player2_board_root
+        };
+        if slot.is_some() {
+            return Err(Error::BoardAlreadyCommitted);
+        }
+        *slot = Some(root);
+
+        if self.player1_board_root.is_some() && self.player2_board_root.is_some() {
+            self.status = Status::InProgress;
+        }
+        Ok(())
+    }
+}
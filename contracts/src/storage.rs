@@ -0,0 +1,30 @@
+use soroban_sdk::{contracttype, Address, BytesN, Env};
+
+use crate::error::Error;
+use crate::vk::Circuit;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// The full on-chain game state, keyed by session.
+    Session(u32),
+    /// Marks a `Poseidon(session_id, cell_index, shooter_secret)` nullifier
+    /// as spent, keyed by session, to block replayed or duplicate shots.
+    Nullifier(u32, BytesN<32>),
+    /// The address allowed to set verifying keys and the sanctioned image id.
+    Admin,
+    /// A circuit's Groth16 verifying key, set by the admin.
+    VerifyingKey(Circuit),
+    /// The RISC Zero guest image id sanctioned for the current game version.
+    ExpectedImageId,
+}
+
+pub fn require_admin(env: &Env) -> Result<Address, Error> {
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(Error::NotInitialized)?;
+    admin.require_auth();
+    Ok(admin)
+}
@@ -1,25 +1,429 @@
-#![cfg(test)]
-use super::*;
-use soroban_sdk::{testutils::Address as _, Env};
-
-#[test]
-fn test_game_flow() {
-    let env = Env::default();
-    let contract_id = env.register_contract(None, ZKStrategyGame);
-    let client = ZKStrategyGameClient::new(&env, &contract_id);
-
-    let player = Address::generate(&env);
-
-    // 1. Test Start Game (Ensure it doesn't crash)
-    // Note: In a real test, you'd need to mock the Hub contract 
-    // or just test the internal state logic.
-    client.start_game(&player);
-
-    // 2. Test Verify Miss
-    let dummy_proof = Bytes::
-    (&env, &[0; 32]);
-    let dummy_inputs = Vec::from_array(&env, [Bytes::from_slice(&env, &[0; 32])]);
-    
-    // This should pass based on our current placeholder implementation
-    client.verify_miss(&dummy_proof, &dummy_inputs);
-}
\ No newline at end of file
+#![cfg(test)]
+use super::*;
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::{Address, Bytes, BytesN, Env, Vec};
+
+use crate::vk::{Circuit, StoredVerifyingKey};
+
+fn session(
+    env: &Env,
+    player1: &Address,
+    player2: &Address,
+    status: Status,
+    player1_board_root: Option<BytesN<32>>,
+    player2_board_root: Option<BytesN<32>>,
+) -> GameSession {
+    GameSession {
+        player1: player1.clone(),
+        player2: player2.clone(),
+        player1_board_root,
+        player2_board_root,
+        whose_turn: player1.clone(),
+        player1_hits_remaining: TOTAL_SHIP_CELLS,
+        player2_hits_remaining: TOTAL_SHIP_CELLS,
+        player1_ships_remaining: TOTAL_SHIPS,
+        player2_ships_remaining: TOTAL_SHIPS,
+        player1_fired: Vec::new(env),
+        player2_fired: Vec::new(env),
+        pending_shot: None,
+        pending_shot_deadline: None,
+        status,
+    }
+}
+
+/// Seeds a session directly into persistent storage, bypassing `start_game`'s
+/// call into the game hub so these tests can focus on this contract's own
+/// logic.
+fn seed_session(env: &Env, contract_id: &Address, session_id: u32, session: &GameSession) {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Session(session_id), session);
+    });
+}
+
+fn dummy_verifying_key(env: &Env) -> StoredVerifyingKey {
+    StoredVerifyingKey {
+        alpha_g1: BytesN::from_array(env, &[0; 96]),
+        beta_g2: BytesN::from_array(env, &[0; 192]),
+        gamma_g2: BytesN::from_array(env, &[0; 192]),
+        delta_g2: BytesN::from_array(env, &[0; 192]),
+        ic: Vec::new(env),
+    }
+}
+
+fn setup() -> (Env, Address, ZKStrategyGameClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, ZKStrategyGame);
+    let client = ZKStrategyGameClient::new(&env, &contract_id);
+    let player1 = Address::generate(&env);
+    let player2 = Address::generate(&env);
+    (env, contract_id, client, player1, player2)
+}
+
+#[test]
+fn test_commit_board_transitions_session_to_in_progress() {
+    let (env, contract_id, client, player1, player2) = setup();
+    let root1 = BytesN::from_array(&env, &[1; 32]);
+    let root2 = BytesN::from_array(&env, &[2; 32]);
+    seed_session(
+        &env,
+        &contract_id,
+        1,
+        &session(&env, &player1, &player2, Status::AwaitingCommit, None, None),
+    );
+
+    client.commit_board(&1, &player1, &root1);
+    assert_eq!(client.session_status(&1), Status::AwaitingCommit);
+
+    client.commit_board(&1, &player2, &root2);
+    assert_eq!(client.session_status(&1), Status::InProgress);
+}
+
+#[test]
+fn test_commit_board_rejects_non_participant() {
+    let (env, contract_id, client, player1, player2) = setup();
+    let outsider = Address::generate(&env);
+    seed_session(
+        &env,
+        &contract_id,
+        1,
+        &session(&env, &player1, &player2, Status::AwaitingCommit, None, None),
+    );
+
+    let root = BytesN::from_array(&env, &[1; 32]);
+    assert_eq!(
+        client.try_commit_board(&1, &outsider, &root),
+        Err(Ok(Error::UnknownSession))
+    );
+}
+
+#[test]
+fn test_commit_board_rejects_double_commit() {
+    let (env, contract_id, client, player1, player2) = setup();
+    let root1 = BytesN::from_array(&env, &[1; 32]);
+    seed_session(
+        &env,
+        &contract_id,
+        1,
+        &session(
+            &env,
+            &player1,
+            &player2,
+            Status::AwaitingCommit,
+            Some(root1.clone()),
+            None,
+        ),
+    );
+
+    assert_eq!(
+        client.try_commit_board(&1, &player1, &root1),
+        Err(Ok(Error::BoardAlreadyCommitted))
+    );
+}
+
+#[test]
+fn test_fire_rejects_second_pending_shot() {
+    let (env, contract_id, client, player1, player2) = setup();
+    let root1 = BytesN::from_array(&env, &[1; 32]);
+    let root2 = BytesN::from_array(&env, &[2; 32]);
+    seed_session(
+        &env,
+        &contract_id,
+        1,
+        &session(
+            &env,
+            &player1,
+            &player2,
+            Status::InProgress,
+            Some(root1),
+            Some(root2),
+        ),
+    );
+
+    client.fire(&1, &5);
+    assert_eq!(client.try_fire(&1, &6), Err(Ok(Error::ShotAlreadyPending)));
+}
+
+#[test]
+fn test_fire_rejects_already_fired_cell() {
+    let (env, contract_id, client, player1, player2) = setup();
+    let root1 = BytesN::from_array(&env, &[1; 32]);
+    let root2 = BytesN::from_array(&env, &[2; 32]);
+    let mut initial = session(
+        &env,
+        &player1,
+        &player2,
+        Status::InProgress,
+        Some(root1),
+        Some(root2),
+    );
+    initial.player1_fired.push_back(5);
+    seed_session(&env, &contract_id, 1, &initial);
+
+    assert_eq!(client.try_fire(&1, &5), Err(Ok(Error::CellAlreadyFired)));
+}
+
+#[test]
+fn test_verify_miss_rejects_board_root_mismatch() {
+    let (env, contract_id, client, player1, player2) = setup();
+    let stored_root = BytesN::from_array(&env, &[1; 32]);
+    let other_root = BytesN::from_array(&env, &[9; 32]);
+    let mut initial = session(
+        &env,
+        &player1,
+        &player2,
+        Status::InProgress,
+        Some(stored_root),
+        Some(BytesN::from_array(&env, &[2; 32])),
+    );
+    initial.pending_shot = Some(3);
+    seed_session(&env, &contract_id, 1, &initial);
+
+    client.initialize(&Address::generate(&env));
+    client.set_verifying_key(&Circuit::Miss, &dummy_verifying_key(&env));
+
+    let proof = Bytes::from_array(&env, &[0; 384]);
+    let public_inputs = Vec::from_array(
+        &env,
+        [
+            Bytes::from(other_root),
+            Bytes::from(BytesN::from_array(&env, &[0; 32])),
+        ],
+    );
+
+    assert_eq!(
+        client.try_verify_miss(&1, &proof, &public_inputs),
+        Err(Ok(Error::BoardRootMismatch))
+    );
+}
+
+#[test]
+fn test_verify_miss_rejects_already_spent_nullifier() {
+    let (env, contract_id, client, player1, player2) = setup();
+    let stored_root = BytesN::from_array(&env, &[1; 32]);
+    let nullifier = BytesN::from_array(&env, &[7; 32]);
+    let mut initial = session(
+        &env,
+        &player1,
+        &player2,
+        Status::InProgress,
+        Some(BytesN::from_array(&env, &[2; 32])),
+        Some(stored_root.clone()),
+    );
+    initial.pending_shot = Some(3);
+    seed_session(&env, &contract_id, 1, &initial);
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Nullifier(1, nullifier.clone()), &true);
+    });
+
+    client.initialize(&Address::generate(&env));
+    client.set_verifying_key(&Circuit::Miss, &dummy_verifying_key(&env));
+
+    let proof = Bytes::from_array(&env, &[0; 384]);
+    let public_inputs = Vec::from_array(
+        &env,
+        [
+            Bytes::from(stored_root),
+            Bytes::from(nullifier),
+            Bytes::from_array(&env, &encode_cell(3)),
+        ],
+    );
+
+    assert_eq!(
+        client.try_verify_miss(&1, &proof, &public_inputs),
+        Err(Ok(Error::NullifierAlreadySpent))
+    );
+}
+
+#[test]
+fn test_verify_miss_rejects_cell_mismatch() {
+    let (env, contract_id, client, player1, player2) = setup();
+    let stored_root = BytesN::from_array(&env, &[1; 32]);
+    let mut initial = session(
+        &env,
+        &player1,
+        &player2,
+        Status::InProgress,
+        Some(BytesN::from_array(&env, &[2; 32])),
+        Some(stored_root.clone()),
+    );
+    initial.pending_shot = Some(3);
+    seed_session(&env, &contract_id, 1, &initial);
+
+    client.initialize(&Address::generate(&env));
+    client.set_verifying_key(&Circuit::Miss, &dummy_verifying_key(&env));
+
+    let proof = Bytes::from_array(&env, &[0; 384]);
+    let public_inputs = Vec::from_array(
+        &env,
+        [
+            Bytes::from(stored_root),
+            Bytes::from(BytesN::from_array(&env, &[0; 32])),
+            Bytes::from_array(&env, &encode_cell(4)),
+        ],
+    );
+
+    assert_eq!(
+        client.try_verify_miss(&1, &proof, &public_inputs),
+        Err(Ok(Error::InvalidProof))
+    );
+}
+
+#[test]
+fn test_verify_sink_rejects_non_participant_defender() {
+    let (env, contract_id, client, player1, player2) = setup();
+    let outsider = Address::generate(&env);
+    let root1 = BytesN::from_array(&env, &[1; 32]);
+    let root2 = BytesN::from_array(&env, &[2; 32]);
+    seed_session(
+        &env,
+        &contract_id,
+        1,
+        &session(
+            &env,
+            &player1,
+            &player2,
+            Status::InProgress,
+            Some(root1),
+            Some(root2),
+        ),
+    );
+
+    let proof = Bytes::from_array(&env, &[0; 384]);
+    let public_inputs = Vec::new(&env);
+    assert_eq!(
+        client.try_verify_sink(&1, &outsider, &proof, &public_inputs),
+        Err(Ok(Error::UnknownSession))
+    );
+}
+
+#[test]
+fn test_session_status_rejects_unknown_session() {
+    let (_env, _contract_id, client, _player1, _player2) = setup();
+    assert_eq!(
+        client.try_session_status(&1),
+        Err(Ok(Error::UnknownSession))
+    );
+}
+
+#[test]
+fn test_resolve_shot_declares_winner_on_final_hit() {
+    let env = Env::default();
+    let player1 = Address::generate(&env);
+    let player2 = Address::generate(&env);
+    let mut game = session(
+        &env,
+        &player1,
+        &player2,
+        Status::InProgress,
+        Some(BytesN::from_array(&env, &[1; 32])),
+        Some(BytesN::from_array(&env, &[2; 32])),
+    );
+    game.player2_hits_remaining = 1;
+
+    let winner = game.resolve_shot(0, true);
+
+    assert_eq!(winner, Some(player1));
+    assert_eq!(game.status, Status::Finished);
+}
+
+#[test]
+fn test_resolve_shot_hands_off_turn_on_miss() {
+    let env = Env::default();
+    let player1 = Address::generate(&env);
+    let player2 = Address::generate(&env);
+    let mut game = session(
+        &env,
+        &player1,
+        &player2,
+        Status::InProgress,
+        Some(BytesN::from_array(&env, &[1; 32])),
+        Some(BytesN::from_array(&env, &[2; 32])),
+    );
+
+    let winner = game.resolve_shot(0, false);
+
+    assert_eq!(winner, None);
+    assert_eq!(game.whose_turn, player2);
+    assert_eq!(game.status, Status::InProgress);
+}
+
+#[test]
+fn test_claim_shot_timeout_rejects_before_deadline() {
+    let (env, contract_id, client, player1, player2) = setup();
+    let root1 = BytesN::from_array(&env, &[1; 32]);
+    let root2 = BytesN::from_array(&env, &[2; 32]);
+    let mut initial = session(
+        &env,
+        &player1,
+        &player2,
+        Status::InProgress,
+        Some(root1),
+        Some(root2),
+    );
+    initial.pending_shot = Some(3);
+    initial.pending_shot_deadline = Some(1_000);
+    seed_session(&env, &contract_id, 1, &initial);
+    env.ledger().with_mut(|li| li.timestamp = 500);
+
+    assert_eq!(
+        client.try_claim_shot_timeout(&1),
+        Err(Ok(Error::ShotResponseNotTimedOut))
+    );
+}
+
+#[test]
+fn test_claim_shot_timeout_resolves_as_hit_after_deadline() {
+    let (env, contract_id, client, player1, player2) = setup();
+    let root1 = BytesN::from_array(&env, &[1; 32]);
+    let root2 = BytesN::from_array(&env, &[2; 32]);
+    let mut initial = session(
+        &env,
+        &player1,
+        &player2,
+        Status::InProgress,
+        Some(root1),
+        Some(root2),
+    );
+    initial.whose_turn = player1.clone();
+    initial.pending_shot = Some(3);
+    initial.pending_shot_deadline = Some(1_000);
+    seed_session(&env, &contract_id, 1, &initial);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    client.claim_shot_timeout(&1);
+
+    let updated: GameSession = env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Session(1))
+            .unwrap()
+    });
+    assert_eq!(updated.pending_shot, None);
+    assert_eq!(updated.player2_hits_remaining, TOTAL_SHIP_CELLS - 1);
+    assert_eq!(updated.whose_turn, player2);
+}
+
+#[test]
+fn test_record_sink_decrements_defenders_ships_remaining() {
+    let env = Env::default();
+    let player1 = Address::generate(&env);
+    let player2 = Address::generate(&env);
+    let mut game = session(
+        &env,
+        &player1,
+        &player2,
+        Status::InProgress,
+        Some(BytesN::from_array(&env, &[1; 32])),
+        Some(BytesN::from_array(&env, &[2; 32])),
+    );
+
+    game.record_sink(&player2);
+
+    assert_eq!(game.player2_ships_remaining, TOTAL_SHIPS - 1);
+    assert_eq!(game.player1_ships_remaining, TOTAL_SHIPS);
+}
@@ -0,0 +1,95 @@
+//! Groth16 verifying keys for the game's circuits.
+//!
+//! Real verifying keys come out of each circuit's trusted setup and differ
+//! per deployment, so they are not baked into the contract: the admin loads
+//! them into instance storage once via `set_verifying_key`, and every
+//! verification entrypoint reads them back from there.
+use soroban_sdk::crypto::bls12_381::{G1Affine, G2Affine};
+use soroban_sdk::{contracttype, Address, BytesN, Env, Vec};
+
+use crate::error::Error;
+use crate::groth16::VerifyingKey;
+use crate::storage::{self, DataKey};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum Circuit {
+    /// Public inputs: `[root, nullifier, cell]`, so its verifying key's `ic`
+    /// needs 4 entries (the constant term plus one per input).
+    Miss,
+    /// Same public-input schema as `Miss`.
+    Hit,
+    Sink,
+    Risc0,
+}
+
+/// Wire format for a verifying key: fixed-size curve points so it round-trips
+/// through contract storage without relying on the host's BLS12-381 object
+/// types being storable directly.
+#[contracttype]
+#[derive(Clone)]
+pub struct StoredVerifyingKey {
+    pub alpha_g1: BytesN<96>,
+    pub beta_g2: BytesN<192>,
+    pub gamma_g2: BytesN<192>,
+    pub delta_g2: BytesN<192>,
+    pub ic: Vec<BytesN<96>>,
+}
+
+/// Admin-only: loads a circuit's verifying key into storage. Call once per
+/// circuit after `initialize`, and again whenever the circuit is upgraded.
+pub fn set_verifying_key(env: &Env, circuit: Circuit, vk: StoredVerifyingKey) -> Result<(), Error> {
+    storage::require_admin(env)?;
+    env.storage()
+        .instance()
+        .set(&DataKey::VerifyingKey(circuit), &vk);
+    Ok(())
+}
+
+pub fn load_verifying_key(env: &Env, circuit: Circuit) -> Result<VerifyingKey, Error> {
+    let stored: StoredVerifyingKey = env
+        .storage()
+        .instance()
+        .get(&DataKey::VerifyingKey(circuit))
+        .ok_or(Error::VerifyingKeyNotSet)?;
+
+    let mut ic = Vec::new(env);
+    for point in stored.ic.iter() {
+        ic.push_back(G1Affine::from_array(env, &point.to_array()));
+    }
+    Ok(VerifyingKey {
+        alpha_g1: G1Affine::from_array(env, &stored.alpha_g1.to_array()),
+        beta_g2: G2Affine::from_array(env, &stored.beta_g2.to_array()),
+        gamma_g2: G2Affine::from_array(env, &stored.gamma_g2.to_array()),
+        delta_g2: G2Affine::from_array(env, &stored.delta_g2.to_array()),
+        ic,
+    })
+}
+
+/// Admin-only: sets the RISC Zero guest image id sanctioned for the current
+/// game version; any other `image_id` is rejected even if its seal verifies.
+pub fn set_expected_image_id(env: &Env, image_id: BytesN<32>) -> Result<(), Error> {
+    storage::require_admin(env)?;
+    env.storage()
+        .instance()
+        .set(&DataKey::ExpectedImageId, &image_id);
+    Ok(())
+}
+
+pub fn expected_image_id(env: &Env) -> Result<BytesN<32>, Error> {
+    env.storage()
+        .instance()
+        .get(&DataKey::ExpectedImageId)
+        .ok_or(Error::ImageIdNotSet)
+}
+
+/// Sets the admin once at deployment. Changing an existing admin requires the
+/// current admin's own authorization.
+pub fn set_admin(env: &Env, admin: Address) -> Result<(), Error> {
+    if let Some(current) = env.storage().instance().get::<_, Address>(&DataKey::Admin) {
+        current.require_auth();
+    }
+    admin.require_auth();
+    env.storage().instance().set(&DataKey::Admin, &admin);
+    Ok(())
+}